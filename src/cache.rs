@@ -0,0 +1,173 @@
+use crate::lsh::HashKind;
+use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
+use rustc_hash::FxHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+
+/// Header recorded alongside cached signatures so a cache built for a
+/// different `num_perm`, permutation seed, or `HashKind` is never reused
+/// against mismatched permutations or signatures.
+#[derive(Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+struct CacheHeader {
+    num_perm: usize,
+    seed: u64,
+    kind: HashKind,
+}
+
+#[derive(Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+struct CacheFile {
+    header: CacheHeader,
+    entries: HashMap<u64, Vec<u32>>,
+}
+
+/// An on-disk cache of MinHash signatures keyed by a digest of their
+/// normalized token stream, so repeated dedup runs over overlapping
+/// datasets skip recomputing signatures that haven't changed.
+pub(crate) struct SignatureCache {
+    path: String,
+    num_perm: usize,
+    seed: u64,
+    kind: HashKind,
+    entries: HashMap<u64, Vec<u32>>,
+    dirty: bool,
+}
+
+impl SignatureCache {
+    /// Loads the cache at `path` if it exists and was built for `num_perm`
+    /// and `kind`. In that case the cache's recorded seed is reused so
+    /// permutations can be regenerated identically to the run that
+    /// populated it; otherwise a fresh seed is minted and the cache starts
+    /// empty.
+    pub(crate) fn load(path: &str, num_perm: usize, kind: HashKind, fresh_seed: u64) -> Self {
+        let loaded = fs::read(path)
+            .ok()
+            .and_then(|bytes| rkyv::from_bytes::<CacheFile>(&bytes).ok())
+            .filter(|cache| cache.header.num_perm == num_perm && cache.header.kind == kind);
+        match loaded {
+            Some(cache) => SignatureCache {
+                path: path.to_string(),
+                num_perm,
+                seed: cache.header.seed,
+                kind,
+                entries: cache.entries,
+                dirty: false,
+            },
+            None => SignatureCache {
+                path: path.to_string(),
+                num_perm,
+                seed: fresh_seed,
+                kind,
+                entries: HashMap::new(),
+                dirty: false,
+            },
+        }
+    }
+
+    /// The permutation seed this cache's signatures were computed with.
+    /// Regenerate permutations from this seed to keep cache hits valid.
+    pub(crate) fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Looks up a cached signature for the normalized token stream `items`.
+    pub(crate) fn get(&self, items: &[&str]) -> Option<&Vec<u32>> {
+        self.entries.get(&Self::digest(items))
+    }
+
+    /// Records a freshly computed signature for `items`, to be persisted on
+    /// the next `flush`.
+    pub(crate) fn insert(&mut self, items: &[&str], hash_values: Vec<u32>) {
+        self.entries.insert(Self::digest(items), hash_values);
+        self.dirty = true;
+    }
+
+    fn digest(items: &[&str]) -> u64 {
+        let mut hasher = FxHasher::default();
+        for item in items {
+            item.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Writes the cache back to disk, if any entries were added since it
+    /// was loaded.
+    pub(crate) fn flush(&self) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let cache_file = CacheFile {
+            header: CacheHeader {
+                num_perm: self.num_perm,
+                seed: self.seed,
+                kind: self.kind,
+            },
+            entries: self.entries.clone(),
+        };
+        let bytes = rkyv::to_bytes::<_, 1024>(&cache_file)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+        fs::write(&self.path, bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> String {
+        let mut path = std::env::temp_dir();
+        path.push(format!("lsh-dedup-cache-test-{}-{}.rkyv", std::process::id(), name));
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn cache_hit_skips_recompute() {
+        let path = temp_path("hit");
+        let items = ["the", "quick", "brown", "fox"];
+        let hash_values = vec![1, 2, 3, 4];
+
+        let mut cache = SignatureCache::load(&path, 4, HashKind::Fx, 42);
+        assert!(cache.get(&items).is_none());
+        cache.insert(&items, hash_values.clone());
+        cache.flush().unwrap();
+
+        let reloaded = SignatureCache::load(&path, 4, HashKind::Fx, 99);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reloaded.get(&items), Some(&hash_values));
+        // The recorded seed is reused, not the fresh one passed to `load`.
+        assert_eq!(reloaded.seed(), 42);
+    }
+
+    #[test]
+    fn num_perm_mismatch_invalidates_cache() {
+        let path = temp_path("num-perm-mismatch");
+        let items = ["the", "quick", "brown", "fox"];
+
+        let mut cache = SignatureCache::load(&path, 4, HashKind::Fx, 42);
+        cache.insert(&items, vec![1, 2, 3, 4]);
+        cache.flush().unwrap();
+
+        let reloaded = SignatureCache::load(&path, 8, HashKind::Fx, 99);
+        std::fs::remove_file(&path).unwrap();
+        assert!(reloaded.get(&items).is_none());
+        assert_eq!(reloaded.seed(), 99);
+    }
+
+    #[test]
+    fn hash_kind_mismatch_invalidates_cache() {
+        let path = temp_path("kind-mismatch");
+        let items = ["the", "quick", "brown", "fox"];
+
+        let mut cache = SignatureCache::load(&path, 4, HashKind::Fx, 42);
+        cache.insert(&items, vec![1, 2, 3, 4]);
+        cache.flush().unwrap();
+
+        let reloaded = SignatureCache::load(&path, 4, HashKind::Xxh3, 99);
+        std::fs::remove_file(&path).unwrap();
+        assert!(reloaded.get(&items).is_none());
+        assert_eq!(reloaded.seed(), 99);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::lsh::MinHashLSH;
+use pyo3::prelude::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Disjoint-set (union-find) structure used to merge transitively similar
+/// records into a single duplicate group, even when two records never
+/// collide directly in the same LSH band.
+///
+/// Backed by maps rather than `id`-indexed `Vec`s, since record ids come
+/// from `MinHashLSH::minhash_index` and are not guaranteed to be a dense
+/// `0..len()` range once `insert`/`remove` have been used.
+struct UnionFind {
+    parent: HashMap<usize, usize>,
+    rank: HashMap<usize, u8>,
+}
+
+impl UnionFind {
+    fn new<I: IntoIterator<Item = usize>>(ids: I) -> Self {
+        let parent: HashMap<usize, usize> = ids.into_iter().map(|id| (id, id)).collect();
+        let rank = parent.keys().map(|&id| (id, 0)).collect();
+        UnionFind { parent, rank }
+    }
+
+    /// Finds the root of `x`, halving the path along the way.
+    fn find(&mut self, x: usize) -> usize {
+        let mut x = x;
+        while self.parent[&x] != x {
+            let grandparent = self.parent[&self.parent[&x]];
+            self.parent.insert(x, grandparent);
+            x = grandparent;
+        }
+        x
+    }
+
+    /// Unions the sets containing `a` and `b`, linking the shallower tree
+    /// under the deeper one.
+    fn union(&mut self, a: usize, b: usize) {
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+        if root_a == root_b {
+            return;
+        }
+        match self.rank[&root_a].cmp(&self.rank[&root_b]) {
+            Ordering::Less => {
+                self.parent.insert(root_a, root_b);
+            }
+            Ordering::Greater => {
+                self.parent.insert(root_b, root_a);
+            }
+            Ordering::Equal => {
+                self.parent.insert(root_b, root_a);
+                *self.rank.get_mut(&root_a).unwrap() += 1;
+            }
+        }
+    }
+}
+
+#[pyclass]
+/// Groups near-duplicate records discovered by a `MinHashLSH` index into
+/// transitively-closed duplicate clusters.
+pub struct DeduplicationTable {
+    groups: HashMap<usize, Vec<usize>>,
+}
+
+#[pymethods]
+impl DeduplicationTable {
+    #[new]
+    /// Builds duplicate groups from an LSH index.
+    ///
+    /// ## Arguments
+    ///
+    /// * `lsh` - The built `MinHashLSH` index to cluster.
+    /// * `threshold` - Jaccard similarity threshold (inclusive) used to decide
+    /// which LSH candidates are unioned into the same group.
+    pub fn new(lsh: MinHashLSH, threshold: Option<f64>) -> Self {
+        let index = lsh.minhash_index();
+        let mut uf = UnionFind::new(index.keys().copied());
+        for (&id, minhash) in index.iter() {
+            let candidates = lsh
+                .query(minhash, threshold)
+                .expect("minhash from the index's own table always matches its hash kind");
+            for &candidate in candidates {
+                uf.union(id, candidate);
+            }
+        }
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &id in index.keys() {
+            let root = uf.find(id);
+            groups.entry(root).or_insert_with(Vec::new).push(id);
+        }
+        DeduplicationTable { groups }
+    }
+
+    /// Returns the duplicate clusters as groups of record ids.
+    pub fn grouped_ids(&self) -> Vec<Vec<usize>> {
+        self.groups.values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lsh::HashKind;
+
+    #[test]
+    fn transitive_groups_merge_across_chained_unions() {
+        let mut uf = UnionFind::new([1, 5, 9, 42]);
+        uf.union(1, 5);
+        uf.union(5, 9);
+        assert_eq!(uf.find(1), uf.find(9));
+        assert_ne!(uf.find(1), uf.find(42));
+    }
+
+    #[test]
+    fn sparse_and_out_of_order_ids_do_not_panic() {
+        // Ids like these show up once `insert`/`remove` have made the id
+        // space sparse; a `Vec`-indexed union-find would panic here.
+        let mut uf = UnionFind::new([7, 3, 100]);
+        uf.union(100, 3);
+        assert_eq!(uf.find(100), uf.find(3));
+        assert_ne!(uf.find(100), uf.find(7));
+    }
+
+    #[test]
+    fn records_never_directly_colliding_still_merge_through_a_bridge() {
+        // `a` and `c` share no tokens and never collide directly, but both
+        // overlap enough with the bridging record `b` to merge transitively.
+        // Each of a~b and b~c has jaccard 5/15; one permutation per band
+        // makes those pairs collide in some band all but certainly.
+        let a = "red green blue yellow purple orange pink cyan lime teal";
+        let b = "red green blue yellow purple square circle triangle hexagon pentagon";
+        let c = "square circle triangle hexagon pentagon octagon rectangle oval diamond star";
+        let lsh = MinHashLSH::new(
+            vec![a.to_string(), b.to_string(), c.to_string()],
+            128,
+            128,
+            HashKind::Fx,
+        );
+        let dedup_table = DeduplicationTable::new(lsh, Some(0.2));
+        let groups = dedup_table.grouped_ids();
+        assert_eq!(groups.len(), 1, "expected a, b, c in a single group: {groups:?}");
+        let mut ids = groups[0].clone();
+        ids.sort();
+        assert_eq!(ids, vec![0, 1, 2]);
+    }
+}
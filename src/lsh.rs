@@ -1,24 +1,122 @@
+use crate::cache::SignatureCache;
+use crc32fast::Hasher as Crc32Hasher;
 use indicatif::ProgressIterator;
+use memmap2::Mmap;
+use pyo3::exceptions::{PyIOError, PyValueError};
 use pyo3::prelude::*;
 use rand::prelude::*;
 use rayon::prelude::*;
+use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
 use rustc_hash::FxHasher;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::File;
 use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use twox_hash::Xxh3Hash64;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
+#[pyclass(eq)]
+/// The hash function backing a `MinHash`'s permutations and an index's band
+/// hashes. Persisted indexes should be built and queried with the same
+/// `HashKind` throughout, since signatures are not portable across kinds.
+pub enum HashKind {
+    /// `rustc_hash::FxHasher`. Fast, but not stable across platforms. Default.
+    Fx,
+    /// `xxhash`'s 64-bit XXH3 variant. Good mixing, stable across platforms.
+    Xxh3,
+    /// CRC-32. Slower and lower quality, but ubiquitous and simple to verify.
+    Crc32,
+}
+
+impl Default for HashKind {
+    fn default() -> Self {
+        HashKind::Fx
+    }
+}
+
+fn new_hasher(kind: HashKind) -> Box<dyn Hasher> {
+    match kind {
+        HashKind::Fx => Box::new(FxHasher::default()),
+        HashKind::Xxh3 => Box::new(Xxh3Hash64::default()),
+        HashKind::Crc32 => Box::new(Crc32Hasher::new()),
+    }
+}
+
+/// Error returned when a `MinHash` built with one `HashKind` is queried
+/// against an index built with another; their signatures are not comparable.
+#[derive(Debug)]
+pub struct HashKindMismatch {
+    pub minhash_kind: HashKind,
+    pub index_kind: HashKind,
+}
+
+impl fmt::Display for HashKindMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "queried MinHash has hash kind {:?} but the index was built with {:?}",
+            self.minhash_kind, self.index_kind
+        )
+    }
+}
+
+impl std::error::Error for HashKindMismatch {}
+
+/// Error returned by `query_band` when the queried `band_id` does not match
+/// the single band a shard built by `new_band_shard` is responsible for.
+#[derive(Debug)]
+pub struct BandShardMismatch {
+    pub queried_band_id: usize,
+    pub shard_band_id: usize,
+}
+
+impl fmt::Display for BandShardMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "queried band {} but this shard was built for band {}",
+            self.queried_band_id, self.shard_band_id
+        )
+    }
+}
+
+impl std::error::Error for BandShardMismatch {}
+
+/// Error returned by `query_band`.
+#[derive(Debug)]
+pub enum QueryBandError {
+    HashKind(HashKindMismatch),
+    BandShard(BandShardMismatch),
+}
+
+impl fmt::Display for QueryBandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryBandError::HashKind(e) => e.fmt(f),
+            QueryBandError::BandShard(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for QueryBandError {}
+
+#[derive(Clone, Debug, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
 #[pyclass]
 pub struct MinHash {
     pub hash_values: Vec<u32>,
     num_perm: usize,
+    kind: HashKind,
 }
 
 impl MinHash {
-    fn new(items: Vec<&str>, permutations: &Vec<(u64, u64)>) -> Self {
+    fn new(items: Vec<&str>, permutations: &Vec<(u64, u64)>, kind: HashKind) -> Self {
         let num_perm = permutations.len();
         let mut hash_values = vec![u32::MAX; num_perm];
         for item in items {
-            let item_hash = calculate_hash(&item);
+            let item_hash = calculate_hash(&item, kind);
             for (i, &(a, b)) in permutations.iter().enumerate() {
                 let hash = permute_hash(item_hash, a, b);
                 hash_values[i] = hash_values[i].min(hash);
@@ -27,6 +125,7 @@ impl MinHash {
         MinHash {
             hash_values,
             num_perm,
+            kind,
         }
     }
 
@@ -39,6 +138,17 @@ impl MinHash {
             .count();
         equal_count as f64 / self.num_perm as f64
     }
+
+    /// Builds a MinHash directly from a previously computed signature,
+    /// skipping the permutation loop in `new` (used to serve cache hits).
+    pub(crate) fn from_hash_values(hash_values: Vec<u32>, kind: HashKind) -> Self {
+        let num_perm = hash_values.len();
+        MinHash {
+            hash_values,
+            num_perm,
+            kind,
+        }
+    }
 }
 
 #[pymethods]
@@ -53,7 +163,8 @@ impl MinHash {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Archive, RkyvSerialize, RkyvDeserialize)]
+#[archive(check_bytes)]
 #[pyclass]
 /// Locality-Sensitive Hashing using MinHash for efficient similarity search.
 pub struct MinHashLSH {
@@ -63,6 +174,18 @@ pub struct MinHashLSH {
     band_size: usize,
     /// Banded hash tables used to find candidates for similarity
     hash_tables: Vec<HashMap<u64, Vec<usize>>>,
+    /// The MinHash permutations used to build this index, kept so a loaded
+    /// index can only query MinHashes produced from the same permutations.
+    permutations: Vec<(u64, u64)>,
+    /// The next id to hand out for an auto-assigned insert, kept monotonic
+    /// and collision-free across inserts and removals.
+    next_id: usize,
+    /// The hash function backing this index's MinHashes and band hashes.
+    kind: HashKind,
+    /// `Some(band_id)` when this index is a single-band shard built by
+    /// `new_band_shard`, so `query_band` can reject a mismatched `band_id`
+    /// instead of silently returning candidates for the wrong band.
+    shard_band_id: Option<usize>,
 }
 
 #[pymethods]
@@ -75,8 +198,11 @@ impl MinHashLSH {
     /// * `num_perm` - Number of permutations to use in the MinHash algorithm.
     /// * `num_bands` - Number of times to split each hash signature in the LSH algorithm
     /// (i.e., number of hash tables).
+    /// * `kind` - The hash function to back the MinHash permutations and band hashes with
+    /// (defaults to `HashKind::Fx` to preserve current performance).
     #[new]
-    pub fn new(records: Vec<String>, num_perm: usize, num_bands: usize) -> Self {
+    #[pyo3(signature = (records, num_perm, num_bands, kind=HashKind::Fx))]
+    pub fn new(records: Vec<String>, num_perm: usize, num_bands: usize, kind: HashKind) -> Self {
         let mut rng = StdRng::from_entropy();
         let permutations: Vec<(u64, u64)> = (0..num_perm).map(|_| (rng.gen(), rng.gen())).collect();
         let band_size = num_perm / num_bands;
@@ -84,12 +210,12 @@ impl MinHashLSH {
         let mut hash_tables: Vec<HashMap<u64, Vec<usize>>> = vec![HashMap::new(); num_bands];
         for (id, text) in records.iter().enumerate().progress() {
             let items = text.split_whitespace().collect();
-            let minhash = MinHash::new(items, &permutations);
+            let minhash = MinHash::new(items, &permutations, kind);
             minhash_index.insert(id, minhash.clone());
             for (i, table) in hash_tables.iter_mut().enumerate() {
                 let start = i * band_size;
                 let end = start + band_size;
-                let band_hash = calculate_band_hash(&minhash.hash_values[start..end]);
+                let band_hash = calculate_band_hash(&minhash.hash_values[start..end], kind);
                 table.entry(band_hash).or_insert_with(Vec::new).push(id);
             }
         }
@@ -97,6 +223,10 @@ impl MinHashLSH {
             minhash_index,
             band_size,
             hash_tables,
+            permutations,
+            next_id: records.len(),
+            kind,
+            shard_band_id: None,
         }
     }
 
@@ -104,6 +234,7 @@ impl MinHashLSH {
     fn query_py(&self, minhash: &MinHash, threshold: Option<f64>) -> PyResult<Vec<usize>> {
         let result = self
             .query(minhash, threshold)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?
             .into_iter()
             .map(|&u| u)
             .collect();
@@ -113,9 +244,293 @@ impl MinHashLSH {
     fn get_minhash_index(&self) -> HashMap<usize, MinHash> {
         self.minhash_index.clone()
     }
+
+    #[pyo3(name = "save")]
+    fn save_py(&self, path: &str) -> PyResult<()> {
+        self.save(path)
+            .map_err(|e| PyIOError::new_err(e.to_string()))
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "load")]
+    fn load_py(path: &str) -> PyResult<Self> {
+        MinHashLSH::load(path).map_err(|e| PyIOError::new_err(e.to_string()))
+    }
+
+    #[pyo3(name = "insert")]
+    fn insert_py(&mut self, id: usize, text: &str) {
+        self.insert(id, text)
+    }
+
+    #[pyo3(name = "insert_next")]
+    fn insert_next_py(&mut self, text: &str) -> usize {
+        self.insert_next(text)
+    }
+
+    #[pyo3(name = "remove")]
+    fn remove_py(&mut self, id: usize) {
+        self.remove(id)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "new_with_cache", signature = (records, num_perm, num_bands, cache_path, kind=HashKind::Fx))]
+    fn new_with_cache_py(
+        records: Vec<String>,
+        num_perm: usize,
+        num_bands: usize,
+        cache_path: &str,
+        kind: HashKind,
+    ) -> PyResult<Self> {
+        MinHashLSH::new_with_cache(records, num_perm, num_bands, cache_path, kind)
+            .map_err(|e| PyIOError::new_err(e.to_string()))
+    }
 }
 
 impl MinHashLSH {
+    pub(crate) fn minhash_index(&self) -> &HashMap<usize, MinHash> {
+        &self.minhash_index
+    }
+
+    /// Inserts a single record, computing its MinHash from the permutations
+    /// this index was built with.
+    ///
+    /// ## Arguments
+    ///
+    /// * `id` - The id to store the record under. Passing an id at or past
+    /// the current auto-assign cursor advances it, keeping future
+    /// `insert_next` ids collision-free.
+    /// * `text` - The record text.
+    pub fn insert(&mut self, id: usize, text: &str) {
+        // Drop any previous signature's band memberships first, so
+        // re-inserting an existing id under different text can't leave it
+        // as a stale candidate under bands it no longer belongs to.
+        self.remove(id);
+        let items = text.split_whitespace().collect();
+        let minhash = MinHash::new(items, &self.permutations, self.kind);
+        for (i, table) in self.hash_tables.iter_mut().enumerate() {
+            let start = i * self.band_size;
+            let end = start + self.band_size;
+            let band_hash = calculate_band_hash(&minhash.hash_values[start..end], self.kind);
+            table.entry(band_hash).or_insert_with(Vec::new).push(id);
+        }
+        self.minhash_index.insert(id, minhash);
+        self.next_id = self.next_id.max(id + 1);
+    }
+
+    /// Inserts a record under the next auto-assigned id and returns it.
+    pub fn insert_next(&mut self, text: &str) -> usize {
+        let id = self.next_id;
+        self.insert(id, text);
+        id
+    }
+
+    /// Removes a record from the index, dropping it from `minhash_index`
+    /// and from every band bucket it was stored in, cleaning up any bucket
+    /// left empty.
+    pub fn remove(&mut self, id: usize) {
+        let Some(minhash) = self.minhash_index.remove(&id) else {
+            return;
+        };
+        for (i, table) in self.hash_tables.iter_mut().enumerate() {
+            let start = i * self.band_size;
+            let end = start + self.band_size;
+            let band_hash = calculate_band_hash(&minhash.hash_values[start..end], self.kind);
+            if let Some(bucket) = table.get_mut(&band_hash) {
+                bucket.retain(|&existing| existing != id);
+                if bucket.is_empty() {
+                    table.remove(&band_hash);
+                }
+            }
+        }
+    }
+
+    /// Serializes the index to `path` as an `rkyv` archive. Load it back
+    /// with `load` for an owned, fully-deserialized index, or with
+    /// `MappedMinHashLSH::open` to query a multi-gigabyte archive directly
+    /// off a memory-mapped buffer without a full deserialize pass.
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let bytes = rkyv::to_bytes::<_, 1024>(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+        let mut file = File::create(path)?;
+        file.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Loads an index previously written by `save`.
+    ///
+    /// The archive is memory-mapped and validated in place before being
+    /// deserialized, so a corrupt or mismatched archive is rejected without
+    /// first copying the whole file into memory.
+    pub fn load(path: &str) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let mmap = unsafe { Mmap::map(&file)? };
+        let archived = rkyv::check_archived_root::<MinHashLSH>(&mmap)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        let band_size = usize::try_from(archived.band_size).unwrap();
+        // A shard stores only the table for `shard_band_id`; a full index
+        // stores one table per band starting at band 0.
+        let bands_spanned = match archived.shard_band_id.as_ref() {
+            Some(&shard_band_id) => usize::try_from(shard_band_id).unwrap() + 1,
+            None => archived.hash_tables.len(),
+        };
+        if band_size * bands_spanned > archived.permutations.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "archived num_perm/band_size is inconsistent with the stored hash tables",
+            ));
+        }
+        Ok(archived.deserialize(&mut rkyv::Infallible).unwrap())
+    }
+
+    /// Builds a new index like `new`, but backed by an on-disk cache of
+    /// MinHash signatures keyed by a digest of each record's normalized
+    /// token stream. Cache hits skip recomputing the signature entirely;
+    /// misses are computed as usual and added to the cache, which is
+    /// flushed to `cache_path` once the whole index has been built.
+    ///
+    /// ## Arguments
+    ///
+    /// * `records` - The records to dedupe.
+    /// * `num_perm` - Number of permutations to use in the MinHash algorithm.
+    /// * `num_bands` - Number of times to split each hash signature in the LSH algorithm
+    /// (i.e., number of hash tables).
+    /// * `cache_path` - Where the signature cache is read from and flushed to.
+    /// * `kind` - The hash function to back the MinHash permutations and band hashes with
+    /// (defaults to `HashKind::Fx` to preserve current performance). The cache records this
+    /// alongside `num_perm` and invalidates itself if either differs from a prior run.
+    pub fn new_with_cache(
+        records: Vec<String>,
+        num_perm: usize,
+        num_bands: usize,
+        cache_path: &str,
+        kind: HashKind,
+    ) -> io::Result<Self> {
+        let fresh_seed: u64 = rand::thread_rng().gen();
+        let mut cache = SignatureCache::load(cache_path, num_perm, kind, fresh_seed);
+        let mut rng = StdRng::seed_from_u64(cache.seed());
+        let permutations: Vec<(u64, u64)> = (0..num_perm).map(|_| (rng.gen(), rng.gen())).collect();
+        let band_size = num_perm / num_bands;
+        let mut minhash_index: HashMap<usize, MinHash> = HashMap::with_capacity(records.len());
+        let mut hash_tables: Vec<HashMap<u64, Vec<usize>>> = vec![HashMap::new(); num_bands];
+        for (id, text) in records.iter().enumerate().progress() {
+            let items: Vec<&str> = text.split_whitespace().collect();
+            let minhash = match cache.get(&items) {
+                Some(hash_values) => MinHash::from_hash_values(hash_values.clone(), kind),
+                None => {
+                    let minhash = MinHash::new(items.clone(), &permutations, kind);
+                    cache.insert(&items, minhash.hash_values.clone());
+                    minhash
+                }
+            };
+            minhash_index.insert(id, minhash.clone());
+            for (i, table) in hash_tables.iter_mut().enumerate() {
+                let start = i * band_size;
+                let end = start + band_size;
+                let band_hash = calculate_band_hash(&minhash.hash_values[start..end], kind);
+                table.entry(band_hash).or_insert_with(Vec::new).push(id);
+            }
+        }
+        cache.flush()?;
+        Ok(MinHashLSH {
+            minhash_index,
+            band_size,
+            hash_tables,
+            permutations,
+            next_id: records.len(),
+            kind,
+            shard_band_id: None,
+        })
+    }
+
+    /// Builds a shard of the index holding only the single band `band_id`,
+    /// so shards can be built and queried independently on separate
+    /// machines and merged at query time with `candidates_union`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `records` - The records to dedupe.
+    /// * `num_perm` - Number of permutations to use in the MinHash algorithm.
+    /// * `num_bands` - The total number of bands the full index is split into.
+    /// * `band_id` - Which of those bands this shard is responsible for.
+    /// * `keep_minhash_index` - Whether to retain full MinHash signatures
+    /// for jaccard filtering, or only the band table (set this on whichever
+    /// shard will hold the full signatures used to apply `threshold`).
+    /// * `kind` - The hash function to back the MinHash permutations and band hashes with.
+    pub fn new_band_shard(
+        records: Vec<String>,
+        num_perm: usize,
+        num_bands: usize,
+        band_id: usize,
+        keep_minhash_index: bool,
+        kind: HashKind,
+    ) -> Self {
+        let mut rng = StdRng::from_entropy();
+        let permutations: Vec<(u64, u64)> = (0..num_perm).map(|_| (rng.gen(), rng.gen())).collect();
+        let band_size = num_perm / num_bands;
+        let start = band_id * band_size;
+        let end = start + band_size;
+        let mut minhash_index: HashMap<usize, MinHash> = HashMap::new();
+        let mut table: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (id, text) in records.iter().enumerate().progress() {
+            let items = text.split_whitespace().collect();
+            let minhash = MinHash::new(items, &permutations, kind);
+            let band_hash = calculate_band_hash(&minhash.hash_values[start..end], kind);
+            table.entry(band_hash).or_insert_with(Vec::new).push(id);
+            if keep_minhash_index {
+                minhash_index.insert(id, minhash);
+            }
+        }
+        MinHashLSH {
+            minhash_index,
+            band_size,
+            hash_tables: vec![table],
+            permutations,
+            next_id: records.len(),
+            kind,
+            shard_band_id: Some(band_id),
+        }
+    }
+
+    /// Queries this shard's band table for candidate ids.
+    ///
+    /// ## Arguments
+    ///
+    /// * `minhash` - The MinHash instance to query for.
+    /// * `band_id` - The band this shard was built with (`new_band_shard`'s
+    /// `band_id`); used to slice the same hash range out of `minhash`.
+    pub fn query_band(
+        &self,
+        minhash: &MinHash,
+        band_id: usize,
+    ) -> Result<Vec<usize>, QueryBandError> {
+        if minhash.kind != self.kind {
+            return Err(QueryBandError::HashKind(HashKindMismatch {
+                minhash_kind: minhash.kind,
+                index_kind: self.kind,
+            }));
+        }
+        if let Some(shard_band_id) = self.shard_band_id {
+            if shard_band_id != band_id {
+                return Err(QueryBandError::BandShard(BandShardMismatch {
+                    queried_band_id: band_id,
+                    shard_band_id,
+                }));
+            }
+        }
+        let start = band_id * self.band_size;
+        let end = start + self.band_size;
+        let band_hash = calculate_band_hash(&minhash.hash_values[start..end], self.kind);
+        // A shard only ever stores the single table for its own band at
+        // index 0; a full index stores one table per band in order.
+        let table_index = if self.shard_band_id.is_some() { 0 } else { band_id };
+        Ok(self
+            .hash_tables
+            .get(table_index)
+            .and_then(|table| table.get(&band_hash))
+            .cloned()
+            .unwrap_or_default())
+    }
+
     /// Query the LSH index for (potentially) similar items.
     ///
     /// ## Arguments
@@ -123,7 +538,17 @@ impl MinHashLSH {
     /// * `minhash` - The MinHash instance to query for.
     /// * `threshold` - threshold (inclusive) for jaccard similarity to apply to query result (optional) .
     ///
-    pub fn query(&self, minhash: &MinHash, threshold: Option<f64>) -> Vec<&usize> {
+    pub fn query(
+        &self,
+        minhash: &MinHash,
+        threshold: Option<f64>,
+    ) -> Result<Vec<&usize>, HashKindMismatch> {
+        if minhash.kind != self.kind {
+            return Err(HashKindMismatch {
+                minhash_kind: minhash.kind,
+                index_kind: self.kind,
+            });
+        }
         let candidates: HashSet<&usize> =
             self.hash_tables
                 .iter()
@@ -131,14 +556,14 @@ impl MinHashLSH {
                 .fold(HashSet::new(), |mut doc_set, (i, table)| {
                     let start = i * self.band_size;
                     let end = start + self.band_size;
-                    let band_hash = calculate_band_hash(&minhash.hash_values[start..end]);
+                    let band_hash = calculate_band_hash(&minhash.hash_values[start..end], self.kind);
                     if let Some(docs) = table.get(&band_hash) {
                         doc_set.extend(docs);
                     }
                     doc_set
                 });
         if let Some(threshold) = threshold {
-            candidates
+            Ok(candidates
                 .into_par_iter()
                 .filter_map(|idx| {
                     let candidate_hash = &self.minhash_index[&idx];
@@ -148,18 +573,177 @@ impl MinHashLSH {
                         None
                     }
                 })
-                .collect()
+                .collect())
         } else {
-            candidates.into_iter().collect()
+            Ok(candidates.into_iter().collect())
         }
     }
 }
 
+fn hash_kind_from_archived(kind: &ArchivedHashKind) -> HashKind {
+    match kind {
+        ArchivedHashKind::Fx => HashKind::Fx,
+        ArchivedHashKind::Xxh3 => HashKind::Xxh3,
+        ArchivedHashKind::Crc32 => HashKind::Crc32,
+    }
+}
+
+fn jaccard_similarity_archived(minhash: &MinHash, candidate: &ArchivedMinHash) -> f64 {
+    let equal_count = minhash
+        .hash_values
+        .iter()
+        .zip(candidate.hash_values.iter())
+        .filter(|&(a, b)| *a == *b)
+        .count();
+    equal_count as f64 / minhash.num_perm as f64
+}
+
+#[pyclass]
+/// A memory-mapped, read-only view over an archive written by
+/// `MinHashLSH::save`, queried directly against the mapped bytes. Unlike
+/// `MinHashLSH::load`, this never deserializes `hash_tables`/`minhash_index`
+/// into owned collections, so a multi-gigabyte archive can be queried
+/// without a full deserialize pass.
+pub struct MappedMinHashLSH {
+    mmap: Mmap,
+}
+
+impl MappedMinHashLSH {
+    /// Memory-maps the archive at `path` and validates its header, so a
+    /// corrupt or truncated archive is rejected eagerly rather than on the
+    /// first query.
+    pub fn open(path: &str) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let mmap = unsafe { Mmap::map(&file)? };
+        let archived = rkyv::check_archived_root::<MinHashLSH>(&mmap)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        let band_size = usize::try_from(archived.band_size).unwrap();
+        // A shard stores only the table for `shard_band_id`; a full index
+        // stores one table per band starting at band 0.
+        let bands_spanned = match archived.shard_band_id.as_ref() {
+            Some(&shard_band_id) => usize::try_from(shard_band_id).unwrap() + 1,
+            None => archived.hash_tables.len(),
+        };
+        if band_size * bands_spanned > archived.permutations.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "archived num_perm/band_size is inconsistent with the stored hash tables",
+            ));
+        }
+        Ok(MappedMinHashLSH { mmap })
+    }
+
+    fn archived(&self) -> &ArchivedMinHashLSH {
+        // Safety: `open` already validated these exact bytes with
+        // `check_archived_root`, and `mmap` is never mutated afterwards.
+        unsafe { rkyv::archived_root::<MinHashLSH>(&self.mmap) }
+    }
+
+    /// Query the mapped archive for (potentially) similar items, reading
+    /// band hashes and MinHash signatures directly out of the mapped
+    /// buffer instead of an owned, fully-deserialized index.
+    ///
+    /// ## Arguments
+    ///
+    /// * `minhash` - The MinHash instance to query for.
+    /// * `threshold` - threshold (inclusive) for jaccard similarity to apply to query result (optional).
+    pub fn query(
+        &self,
+        minhash: &MinHash,
+        threshold: Option<f64>,
+    ) -> Result<Vec<usize>, HashKindMismatch> {
+        let archived = self.archived();
+        let index_kind = hash_kind_from_archived(&archived.kind);
+        if minhash.kind != index_kind {
+            return Err(HashKindMismatch {
+                minhash_kind: minhash.kind,
+                index_kind,
+            });
+        }
+        let band_size = usize::try_from(archived.band_size).unwrap();
+        let candidates: HashSet<usize> =
+            archived
+                .hash_tables
+                .iter()
+                .enumerate()
+                .fold(HashSet::new(), |mut doc_set, (i, table)| {
+                    let start = i * band_size;
+                    let end = start + band_size;
+                    let band_hash = calculate_band_hash(&minhash.hash_values[start..end], index_kind);
+                    if let Some(docs) = table.get(&band_hash) {
+                        doc_set.extend(
+                            docs.iter()
+                                .map(|&archived_id| usize::try_from(archived_id).unwrap()),
+                        );
+                    }
+                    doc_set
+                });
+        if let Some(threshold) = threshold {
+            Ok(candidates
+                .into_iter()
+                .filter(|idx| {
+                    // `minhash_index`'s archived keys are `ArchivedUsize`, not
+                    // `usize`, so look the id up by scanning rather than a
+                    // typed `.get` that would need an exact `Borrow` match.
+                    archived
+                        .minhash_index
+                        .iter()
+                        .find(|(&archived_id, _)| usize::try_from(archived_id).unwrap() == *idx)
+                        .map(|(_, candidate)| jaccard_similarity_archived(minhash, candidate) >= threshold)
+                        .unwrap_or(false)
+                })
+                .collect())
+        } else {
+            Ok(candidates.into_iter().collect())
+        }
+    }
+}
+
+#[pymethods]
+impl MappedMinHashLSH {
+    #[staticmethod]
+    #[pyo3(name = "open")]
+    fn open_py(path: &str) -> PyResult<Self> {
+        MappedMinHashLSH::open(path).map_err(|e| PyIOError::new_err(e.to_string()))
+    }
+
+    #[pyo3(name = "query", signature = (minhash, threshold=None))]
+    fn query_py(&self, minhash: &MinHash, threshold: Option<f64>) -> PyResult<Vec<usize>> {
+        self.query(minhash, threshold)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+}
+
+/// Unions the per-shard candidate ids returned by independent
+/// `MinHashLSH::query_band` calls, so a coordinator can merge band shards
+/// before applying the jaccard `threshold` filter against whichever shard
+/// holds the full signatures.
+pub fn candidates_union(shards: &[Vec<usize>]) -> HashSet<usize> {
+    shards.iter().flatten().copied().collect()
+}
+
 #[inline]
-fn calculate_hash<T: Hash>(t: &T) -> u64 {
-    let mut s = FxHasher::default();
-    t.hash(&mut s);
-    s.finish()
+fn calculate_hash<T: Hash>(t: &T, kind: HashKind) -> u64 {
+    // `Hash::hash` requires a `Sized` hasher, so this matches on `kind` to
+    // build a concrete hasher per arm rather than going through `new_hasher`'s
+    // `Box<dyn Hasher>`.
+    match kind {
+        HashKind::Fx => {
+            let mut hasher = FxHasher::default();
+            t.hash(&mut hasher);
+            hasher.finish()
+        }
+        HashKind::Xxh3 => {
+            let mut hasher = Xxh3Hash64::default();
+            t.hash(&mut hasher);
+            hasher.finish()
+        }
+        HashKind::Crc32 => {
+            let mut hasher = Crc32Hasher::new();
+            t.hash(&mut hasher);
+            hasher.finish()
+        }
+    }
 }
 
 #[inline]
@@ -168,10 +752,152 @@ fn permute_hash(hash: u64, a: u64, b: u64) -> u32 {
 }
 
 #[inline]
-fn calculate_band_hash(band: &[u32]) -> u64 {
-    let mut hasher = FxHasher::default();
+fn calculate_band_hash(band: &[u32], kind: HashKind) -> u64 {
+    let mut hasher = new_hasher(kind);
     for &value in band {
         hasher.write_u32(value);
     }
     hasher.finish()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> String {
+        let mut path = std::env::temp_dir();
+        path.push(format!("lsh-dedup-test-{}-{}.rkyv", std::process::id(), name));
+        path.to_str().unwrap().to_string()
+    }
+
+    fn sample_lsh() -> MinHashLSH {
+        let records = vec![
+            "the quick brown fox jumps over the lazy dog".to_string(),
+            "the quick brown fox jumps over the lazy cat".to_string(),
+            "a totally unrelated sentence about spaceships".to_string(),
+        ];
+        MinHashLSH::new(records, 64, 16, HashKind::Fx)
+    }
+
+    #[test]
+    fn save_then_load_round_trips_query_results() {
+        let lsh = sample_lsh();
+        let path = temp_path("roundtrip");
+        lsh.save(&path).unwrap();
+        let loaded = MinHashLSH::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        for (&id, minhash) in lsh.minhash_index().iter() {
+            let mut before = lsh.query(minhash, None).unwrap();
+            let mut after = loaded.query(minhash, None).unwrap();
+            before.sort();
+            after.sort();
+            assert_eq!(before, after, "query results differ for id {id} after round-trip");
+        }
+    }
+
+    #[test]
+    fn load_rejects_a_truncated_archive() {
+        let lsh = sample_lsh();
+        let path = temp_path("truncated");
+        lsh.save(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+
+        let result = MinHashLSH::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mapped_query_matches_owned_query() {
+        let lsh = sample_lsh();
+        let path = temp_path("mapped");
+        lsh.save(&path).unwrap();
+        let mapped = MappedMinHashLSH::open(&path).unwrap();
+
+        for (&id, minhash) in lsh.minhash_index().iter() {
+            let mut owned = lsh
+                .query(minhash, Some(0.5))
+                .unwrap()
+                .into_iter()
+                .copied()
+                .collect::<Vec<_>>();
+            let mut via_mmap = mapped.query(minhash, Some(0.5)).unwrap();
+            owned.sort();
+            via_mmap.sort();
+            assert_eq!(owned, via_mmap, "mapped query differs for id {id}");
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn mapped_query_rejects_mismatched_hash_kind() {
+        let lsh = sample_lsh();
+        let path = temp_path("kind-mismatch");
+        lsh.save(&path).unwrap();
+        let mapped = MappedMinHashLSH::open(&path).unwrap();
+
+        let permutations: Vec<(u64, u64)> = (0..64).map(|_| (1, 2)).collect();
+        let other_kind_minhash = MinHash::new(vec!["the", "quick", "brown"], &permutations, HashKind::Xxh3);
+        let result = mapped.query(&other_kind_minhash, None);
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn query_band_on_a_full_index_reads_the_requested_band() {
+        let lsh = sample_lsh();
+        let (&id, minhash) = lsh.minhash_index().iter().next().unwrap();
+        // A full (non-shard) index stores one table per band; every band
+        // should be queryable, not just table 0, and each band's bucket for
+        // an indexed record's own signature holds that record.
+        for band_id in 0..16 {
+            let candidates = lsh.query_band(minhash, band_id).unwrap();
+            assert!(candidates.contains(&id), "band {band_id} missing id {id}");
+        }
+    }
+
+    #[test]
+    fn shard_query_band_matches_the_full_index_for_its_band() {
+        let records = vec![
+            "the quick brown fox jumps over the lazy dog".to_string(),
+            "the quick brown fox jumps over the lazy cat".to_string(),
+            "a totally unrelated sentence about spaceships".to_string(),
+        ];
+        let full = MinHashLSH::new(records.clone(), 64, 16, HashKind::Fx);
+        let band_id = 3;
+        let shard = MinHashLSH::new_band_shard(records, 64, 16, band_id, true, HashKind::Fx);
+
+        for minhash in shard.minhash_index().values() {
+            let mut from_full = full.query_band(minhash, band_id).unwrap();
+            let mut from_shard = shard.query_band(minhash, band_id).unwrap();
+            from_full.sort();
+            from_shard.sort();
+            assert_eq!(from_full, from_shard);
+        }
+    }
+
+    #[test]
+    fn shard_query_band_rejects_a_mismatched_band_id() {
+        let records = vec![
+            "the quick brown fox jumps over the lazy dog".to_string(),
+            "a totally unrelated sentence about spaceships".to_string(),
+        ];
+        let shard = MinHashLSH::new_band_shard(records, 64, 16, 3, true, HashKind::Fx);
+        let minhash = shard.minhash_index().values().next().unwrap().clone();
+
+        let result = shard.query_band(&minhash, 4);
+        assert!(matches!(result, Err(QueryBandError::BandShard(_))));
+    }
+
+    #[test]
+    fn candidates_union_merges_distinct_shard_results() {
+        let shard_a = vec![1, 2, 3];
+        let shard_b = vec![3, 4];
+        let merged = candidates_union(&[shard_a, shard_b]);
+        let mut merged: Vec<usize> = merged.into_iter().collect();
+        merged.sort();
+        assert_eq!(merged, vec![1, 2, 3, 4]);
+    }
+}
@@ -1,8 +1,9 @@
+mod cache;
 mod dedup;
 mod lsh;
 
 use dedup::DeduplicationTable;
-use lsh::{MinHashLSH, Record};
+use lsh::{HashKind, MinHashLSH};
 use std::collections::{HashMap, HashSet};
 
 const FILEPATH: &'static str = "";
@@ -15,6 +16,13 @@ const NUM_PERM: usize = 64;
 const NUM_BANDS: usize = 16;
 const THRESHOLD: f64 = 0.49;
 
+/// A raw CSV row read from `FILEPATH`, kept around only so the output file
+/// can be written back out against the uuids in the input.
+struct Record {
+    uuid: String,
+    text: String,
+}
+
 fn read_csv(filepath: &str, has_header: bool) -> Vec<Record> {
     let mut reader = csv::Reader::from_path(filepath).unwrap();
     let mut records = reader.records();
@@ -26,15 +34,16 @@ fn read_csv(filepath: &str, has_header: bool) -> Vec<Record> {
             let str_rec = rec.unwrap();
             let uuid = str_rec.get(ID_COL).unwrap().to_string();
             let text = str_rec.get(TEXT_COL).unwrap().to_string();
-            Record::new(uuid, text)
+            Record { uuid, text }
         })
         .collect()
 }
 fn main() {
     // input from file
     let records = read_csv(FILEPATH, HAS_HEADER);
+    let texts: Vec<String> = records.iter().map(|rec| rec.text.clone()).collect();
     let start = std::time::Instant::now();
-    let lsh = MinHashLSH::new(records.clone(), NUM_PERM, NUM_BANDS);
+    let lsh = MinHashLSH::new(texts, NUM_PERM, NUM_BANDS, HashKind::Fx);
 
     let dedup_table = DeduplicationTable::new(lsh, Some(THRESHOLD));
     println!(
@@ -50,23 +59,23 @@ fn main() {
 
     // output to file
     let mut writer = csv::Writer::from_path(OUTPUT).unwrap();
-    let mut records: HashMap<String, String> = records
-        .into_iter()
-        .map(|Record { uuid, text }| (uuid, text))
-        .collect();
+    // `MinHashLSH::new` assigns ids by enumeration order, matching `records` here.
+    let mut records: HashMap<usize, Record> = records.into_iter().enumerate().collect();
     let mut docs = HashSet::new();
     for (dupe_id, dupe_group) in dupe_groups.into_iter().enumerate() {
         let ct = &format!("{}", dupe_group.len());
         let dupe_id = &format!("{}", dupe_id);
         for doc_id in dupe_group {
-            let rec = &records.remove(doc_id).unwrap();
+            let rec = records.remove(&doc_id).unwrap();
             if docs.contains(&doc_id) {
                 panic!("dupe doc: {doc_id}")
             } else {
                 docs.insert(doc_id);
             }
             let doc_id = &format!("{doc_id}");
-            writer.write_record([doc_id, rec, dupe_id, ct]).unwrap();
+            writer
+                .write_record([doc_id, &rec.uuid, &rec.text, dupe_id, ct])
+                .unwrap();
         }
     }
 }